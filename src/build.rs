@@ -0,0 +1,80 @@
+use std::fmt;
+
+/// The type of a generated build constant's value.
+///
+/// This mirrors the handful of shapes `shadow-rs` is able to emit into `shadow.rs`:
+/// a string, a boolean, a byte slice, or a slice of strings (used for things like feature
+/// lists).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstType {
+    /// A `&str` constant.
+    Str,
+    /// A `bool` constant.
+    Bool,
+    /// A `&[u8]` constant.
+    Slice,
+    /// A `&[&str]` constant. `v` holds its items comma-joined.
+    StrSlice,
+}
+
+impl fmt::Display for ConstType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            ConstType::Str => "&str",
+            ConstType::Bool => "bool",
+            ConstType::Slice => "&[u8]",
+            ConstType::StrSlice => "&[&str]",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// A single build constant destined for `shadow.rs`.
+///
+/// `v` is always stored as a `String`; for [`ConstType::Bool`] it is parsed with
+/// `str::parse::<bool>`, for [`ConstType::Slice`] it is re-interpreted as `&[u8]` via
+/// `as_bytes()`, and for [`ConstType::StrSlice`] it is split on `,` into `&[&str]`, when
+/// written out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConstVal {
+    /// Human readable description rendered as a doc comment above the constant.
+    pub desc: String,
+    /// The constant's value, pre-formatted as a string.
+    pub v: String,
+    /// The shape the value should be emitted as.
+    pub t: ConstType,
+}
+
+impl ConstVal {
+    pub fn new<S: Into<String>>(desc: S) -> ConstVal {
+        ConstVal {
+            desc: desc.into(),
+            v: String::new(),
+            t: ConstType::Str,
+        }
+    }
+
+    pub fn new_bool<S: Into<String>>(desc: S) -> ConstVal {
+        ConstVal {
+            desc: desc.into(),
+            v: String::new(),
+            t: ConstType::Bool,
+        }
+    }
+
+    pub fn new_slice<S: Into<String>>(desc: S) -> ConstVal {
+        ConstVal {
+            desc: desc.into(),
+            v: String::new(),
+            t: ConstType::Slice,
+        }
+    }
+
+    pub fn new_str_slice<S: Into<String>>(desc: S) -> ConstVal {
+        ConstVal {
+            desc: desc.into(),
+            v: String::new(),
+            t: ConstType::StrSlice,
+        }
+    }
+}
@@ -0,0 +1,240 @@
+use crate::build::ConstVal;
+use crate::ShadowConst;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// The CI system a build is (or isn't) running under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CiType {
+    Gitlab,
+    Github,
+    Circle,
+    Travis,
+    Jenkins,
+    Buildkite,
+    Drone,
+    TeamCity,
+    AppVeyor,
+    None,
+}
+
+impl fmt::Display for CiType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            CiType::Gitlab => "gitlab",
+            CiType::Github => "github",
+            CiType::Circle => "circleci",
+            CiType::Travis => "travis",
+            CiType::Jenkins => "jenkins",
+            CiType::Buildkite => "buildkite",
+            CiType::Drone => "drone",
+            CiType::TeamCity => "teamcity",
+            CiType::AppVeyor => "appveyor",
+            CiType::None => "none",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// One row of the CI signature table: the env var that signals the provider is active, the
+/// `CiType` it maps to, and the env vars that carry the run number, job URL, and commit SHA
+/// for that provider (when known).
+struct CiSignature {
+    signature_env: &'static str,
+    /// Expected value of `signature_env`, or `None` when its mere presence is enough.
+    signature_value: Option<&'static str>,
+    ci_type: CiType,
+    run_number_env: Option<&'static str>,
+    job_url_env: Option<&'static str>,
+    commit_sha_env: Option<&'static str>,
+}
+
+const CI_SIGNATURES: &[CiSignature] = &[
+    CiSignature {
+        signature_env: "GITLAB_CI",
+        signature_value: Some("true"),
+        ci_type: CiType::Gitlab,
+        run_number_env: Some("CI_PIPELINE_IID"),
+        job_url_env: Some("CI_JOB_URL"),
+        commit_sha_env: Some("CI_COMMIT_SHA"),
+    },
+    CiSignature {
+        signature_env: "GITHUB_ACTIONS",
+        signature_value: Some("true"),
+        ci_type: CiType::Github,
+        run_number_env: Some("GITHUB_RUN_NUMBER"),
+        job_url_env: None,
+        commit_sha_env: Some("GITHUB_SHA"),
+    },
+    CiSignature {
+        signature_env: "CIRCLECI",
+        signature_value: Some("true"),
+        ci_type: CiType::Circle,
+        run_number_env: Some("CIRCLE_BUILD_NUM"),
+        job_url_env: Some("CIRCLE_BUILD_URL"),
+        commit_sha_env: Some("CIRCLE_SHA1"),
+    },
+    CiSignature {
+        signature_env: "TRAVIS",
+        signature_value: Some("true"),
+        ci_type: CiType::Travis,
+        run_number_env: Some("TRAVIS_BUILD_NUMBER"),
+        job_url_env: Some("TRAVIS_JOB_WEB_URL"),
+        commit_sha_env: Some("TRAVIS_COMMIT"),
+    },
+    CiSignature {
+        signature_env: "JENKINS_URL",
+        signature_value: None,
+        ci_type: CiType::Jenkins,
+        run_number_env: Some("BUILD_NUMBER"),
+        job_url_env: Some("BUILD_URL"),
+        commit_sha_env: Some("GIT_COMMIT"),
+    },
+    CiSignature {
+        signature_env: "BUILDKITE",
+        signature_value: Some("true"),
+        ci_type: CiType::Buildkite,
+        run_number_env: Some("BUILDKITE_BUILD_NUMBER"),
+        job_url_env: Some("BUILDKITE_BUILD_URL"),
+        commit_sha_env: Some("BUILDKITE_COMMIT"),
+    },
+    CiSignature {
+        signature_env: "DRONE",
+        signature_value: Some("true"),
+        ci_type: CiType::Drone,
+        run_number_env: Some("DRONE_BUILD_NUMBER"),
+        job_url_env: Some("DRONE_BUILD_LINK"),
+        commit_sha_env: Some("DRONE_COMMIT_SHA"),
+    },
+    CiSignature {
+        signature_env: "TEAMCITY_VERSION",
+        signature_value: None,
+        ci_type: CiType::TeamCity,
+        run_number_env: Some("BUILD_NUMBER"),
+        job_url_env: None,
+        commit_sha_env: None,
+    },
+    CiSignature {
+        signature_env: "APPVEYOR",
+        signature_value: Some("True"),
+        ci_type: CiType::AppVeyor,
+        run_number_env: Some("APPVEYOR_BUILD_NUMBER"),
+        job_url_env: None,
+        commit_sha_env: Some("APPVEYOR_REPO_COMMIT"),
+    },
+];
+
+pub const CI_RUN_NUMBER: ShadowConst = "CI_RUN_NUMBER";
+pub const CI_JOB_URL: ShadowConst = "CI_JOB_URL";
+pub const CI_COMMIT_SHA: ShadowConst = "CI_COMMIT_SHA";
+
+/// Walks the signature table and returns the first CI provider whose signature env var is
+/// set (and, where applicable, equal to its expected value).
+pub fn detect_ci(std_env: &BTreeMap<String, String>) -> CiType {
+    for sig in CI_SIGNATURES {
+        match std_env.get(sig.signature_env) {
+            Some(v) => match sig.signature_value {
+                Some(expect) if v != expect => continue,
+                _ => return sig.ci_type,
+            },
+            None => continue,
+        }
+    }
+    CiType::None
+}
+
+/// Build constants for the given `ci_type`, read from that provider's env vars: the
+/// run/build number, the job URL, and the CI-provided commit SHA. Each constant is only
+/// added when the provider has such a var and it is set; signature vars are not checked.
+pub fn new_ci_const(
+    ci_type: CiType,
+    std_env: &BTreeMap<String, String>,
+) -> BTreeMap<ShadowConst, ConstVal> {
+    let mut map = BTreeMap::new();
+
+    let Some(sig) = CI_SIGNATURES.iter().find(|s| s.ci_type == ci_type) else {
+        return map;
+    };
+
+    if let Some(run_number) = sig.run_number_env.and_then(|e| std_env.get(e)) {
+        let mut v = ConstVal::new(format!("The CI run/build number, from `{}`.", sig.run_number_env.unwrap()));
+        v.v = run_number.clone();
+        map.insert(CI_RUN_NUMBER, v);
+    }
+
+    if let Some(job_url) = sig.job_url_env.and_then(|e| std_env.get(e)) {
+        let mut v = ConstVal::new(format!("The CI job URL, from `{}`.", sig.job_url_env.unwrap()));
+        v.v = job_url.clone();
+        map.insert(CI_JOB_URL, v);
+    }
+
+    if let Some(commit_sha) = sig.commit_sha_env.and_then(|e| std_env.get(e)) {
+        let mut v = ConstVal::new(format!(
+            "The commit SHA CI checked out, from `{}`.",
+            sig.commit_sha_env.unwrap()
+        ));
+        v.v = commit_sha.clone();
+        map.insert(CI_COMMIT_SHA, v);
+    }
+
+    map
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_ci_none_when_nothing_set() {
+        assert_eq!(detect_ci(&BTreeMap::new()), CiType::None);
+    }
+
+    #[test]
+    fn test_detect_ci_per_provider() {
+        let cases = [
+            ("GITLAB_CI", "true", CiType::Gitlab),
+            ("GITHUB_ACTIONS", "true", CiType::Github),
+            ("CIRCLECI", "true", CiType::Circle),
+            ("TRAVIS", "true", CiType::Travis),
+            ("JENKINS_URL", "https://jenkins.example", CiType::Jenkins),
+            ("BUILDKITE", "true", CiType::Buildkite),
+            ("DRONE", "true", CiType::Drone),
+            ("TEAMCITY_VERSION", "2023.05", CiType::TeamCity),
+            ("APPVEYOR", "True", CiType::AppVeyor),
+        ];
+
+        for (env_key, env_value, expected) in cases {
+            let mut std_env = BTreeMap::new();
+            std_env.insert(env_key.to_string(), env_value.to_string());
+            assert_eq!(
+                detect_ci(&std_env),
+                expected,
+                "expected {env_key}={env_value} to detect as {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_detect_ci_rejects_wrong_signature_value() {
+        let mut std_env = BTreeMap::new();
+        std_env.insert("GITLAB_CI".to_string(), "false".to_string());
+        assert_eq!(detect_ci(&std_env), CiType::None);
+    }
+
+    #[test]
+    fn test_new_ci_const_pulls_provider_specific_vars() {
+        let mut std_env = BTreeMap::new();
+        std_env.insert("GITHUB_RUN_NUMBER".to_string(), "42".to_string());
+        std_env.insert("GITHUB_SHA".to_string(), "deadbeef".to_string());
+
+        let map = new_ci_const(CiType::Github, &std_env);
+        assert_eq!(map.get(CI_RUN_NUMBER).unwrap().v, "42");
+        assert_eq!(map.get(CI_COMMIT_SHA).unwrap().v, "deadbeef");
+        assert!(map.get(CI_JOB_URL).is_none());
+    }
+
+    #[test]
+    fn test_new_ci_const_empty_for_no_ci() {
+        assert!(new_ci_const(CiType::None, &BTreeMap::new()).is_empty());
+    }
+}
@@ -0,0 +1,80 @@
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A small, dependency-free stand-in for the pieces of `chrono::DateTime` that
+/// `shadow-rs` needs: capturing "now" (or a fixed instant) and rendering it as RFC 2822.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DateTime {
+    secs: i64,
+}
+
+impl DateTime {
+    /// The current wall-clock time.
+    pub fn now() -> DateTime {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        DateTime { secs }
+    }
+
+    /// Build a [`DateTime`] from a Unix timestamp, as produced by `SOURCE_DATE_EPOCH`.
+    pub fn from_unix_timestamp(secs: i64) -> DateTime {
+        DateTime { secs }
+    }
+
+    /// The underlying Unix timestamp, in seconds.
+    pub fn unix_timestamp(&self) -> i64 {
+        self.secs
+    }
+
+    /// Render as an RFC 2822 formatted date-time string, e.g. `Sun, 26 Jul 2026 00:00:00 +0000`.
+    pub fn to_rfc2822(&self) -> String {
+        let (year, month, day, hour, min, sec, weekday) = civil_from_unix(self.secs);
+        const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+        const MONTHS: [&str; 12] = [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+        ];
+        format!(
+            "{}, {:02} {} {:04} {:02}:{:02}:{:02} +0000",
+            WEEKDAYS[weekday as usize],
+            day,
+            MONTHS[(month - 1) as usize],
+            year,
+            hour,
+            min,
+            sec
+        )
+    }
+}
+
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_rfc2822())
+    }
+}
+
+/// Converts a Unix timestamp into `(year, month, day, hour, min, sec, weekday)` using the
+/// proleptic Gregorian calendar. `weekday` is `0` (Monday) through `6` (Sunday).
+fn civil_from_unix(unix: i64) -> (i64, u32, u32, u32, u32, u32, i64) {
+    let days = unix.div_euclid(86_400);
+    let secs_of_day = unix.rem_euclid(86_400);
+    let hour = (secs_of_day / 3600) as u32;
+    let min = ((secs_of_day % 3600) / 60) as u32;
+    let sec = (secs_of_day % 60) as u32;
+
+    // Howard Hinnant's civil_from_days algorithm.
+    let z = days + 719_468;
+    let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+    let doe = (z - era * 146_097) as u64;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let y = yoe as i64 + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = if month <= 2 { y + 1 } else { y };
+
+    let weekday = (days.rem_euclid(7) + 3).rem_euclid(7);
+    (year, month, day, hour, min, sec, weekday)
+}
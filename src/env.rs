@@ -0,0 +1,137 @@
+use crate::build::ConstVal;
+use crate::{Shadow, ShadowConst};
+use std::collections::BTreeMap;
+
+pub const PROJECT_NAME: ShadowConst = "PROJECT_NAME";
+pub const PKG_VERSION: ShadowConst = "PKG_VERSION";
+pub const BUILD_OS: ShadowConst = "BUILD_OS";
+pub const RUST_VERSION: ShadowConst = "RUST_VERSION";
+pub const RUST_CHANNEL: ShadowConst = "RUST_CHANNEL";
+pub const BUILD_TIME: ShadowConst = "BUILD_TIME";
+
+/// Build constants derived from Cargo's own package metadata env vars
+/// (`CARGO_PKG_NAME`, `CARGO_PKG_VERSION`, ...).
+pub fn new_project(std_env: &BTreeMap<String, String>) -> BTreeMap<ShadowConst, ConstVal> {
+    let mut map = BTreeMap::new();
+
+    let mut name = ConstVal::new("The project name, from `CARGO_PKG_NAME`.");
+    name.v = std_env.get("CARGO_PKG_NAME").cloned().unwrap_or_default();
+    map.insert(PROJECT_NAME, name);
+
+    let mut version = ConstVal::new("The project version, from `CARGO_PKG_VERSION`.");
+    version.v = std_env
+        .get("CARGO_PKG_VERSION")
+        .cloned()
+        .unwrap_or_default();
+    map.insert(PKG_VERSION, version);
+
+    map
+}
+
+/// Build constants derived from the toolchain and host operating system.
+pub fn new_system_env(shadow: &Shadow) -> BTreeMap<ShadowConst, ConstVal> {
+    let mut map = BTreeMap::new();
+
+    let mut os = ConstVal::new("The operating system `shadow-rs` was built on.");
+    os.v = std::env::consts::OS.to_string();
+    map.insert(BUILD_OS, os);
+
+    if let Some(rustc) = shadow.std_env.get("RUSTC") {
+        let mut rust_version = ConstVal::new("The `rustc` used to build this project.");
+        rust_version.v = rustc.clone();
+        map.insert(RUST_VERSION, rust_version);
+    }
+
+    let mut build_time = ConstVal::new(
+        "The build time, as RFC 2822. Honors `SOURCE_DATE_EPOCH` for reproducible builds.",
+    );
+    build_time.v = shadow.build_time().to_rfc2822();
+    map.insert(BUILD_TIME, build_time);
+
+    map
+}
+
+pub const BUILD_TARGET: ShadowConst = "BUILD_TARGET";
+pub const BUILD_TARGET_ARCH: ShadowConst = "BUILD_TARGET_ARCH";
+pub const BUILD_TARGET_OS: ShadowConst = "BUILD_TARGET_OS";
+pub const BUILD_OS_FAMILY: ShadowConst = "BUILD_OS_FAMILY";
+pub const BUILD_ENDIAN: ShadowConst = "BUILD_ENDIAN";
+pub const BUILD_POINTER_WIDTH: ShadowConst = "BUILD_POINTER_WIDTH";
+pub const BUILD_HOST: ShadowConst = "BUILD_HOST";
+pub const BUILD_PROFILE: ShadowConst = "BUILD_PROFILE";
+pub const BUILD_OPT_LEVEL: ShadowConst = "BUILD_OPT_LEVEL";
+pub const BUILD_DEBUG: ShadowConst = "BUILD_DEBUG";
+
+/// Build constants describing the compilation target and profile, the way `built` does:
+/// target triple, arch, OS family, endianness, pointer width, and the active
+/// optimization/debug profile. All of these come from the `TARGET`, `HOST`, `PROFILE`,
+/// `OPT_LEVEL`, `DEBUG`, and `CARGO_CFG_*` variables Cargo passes to build scripts.
+pub fn new_compile_env(std_env: &BTreeMap<String, String>) -> BTreeMap<ShadowConst, ConstVal> {
+    let mut map = BTreeMap::new();
+
+    let mut insert = |k: ShadowConst, desc: &str, env_key: &str| {
+        if let Some(v) = std_env.get(env_key) {
+            let mut c = ConstVal::new(format!("{desc} From `{env_key}`."));
+            c.v = v.clone();
+            map.insert(k, c);
+        }
+    };
+
+    insert(BUILD_TARGET, "The compilation target triple.", "TARGET");
+    insert(BUILD_HOST, "The host triple `shadow-rs` was built on.", "HOST");
+    insert(
+        BUILD_TARGET_ARCH,
+        "The target architecture.",
+        "CARGO_CFG_TARGET_ARCH",
+    );
+    insert(
+        BUILD_TARGET_OS,
+        "The target operating system (not necessarily the host the build ran on).",
+        "CARGO_CFG_TARGET_OS",
+    );
+    insert(
+        BUILD_OS_FAMILY,
+        "The target OS family (`unix` or `windows`).",
+        "CARGO_CFG_TARGET_FAMILY",
+    );
+    insert(
+        BUILD_ENDIAN,
+        "The target's endianness.",
+        "CARGO_CFG_TARGET_ENDIAN",
+    );
+    insert(
+        BUILD_POINTER_WIDTH,
+        "The target's pointer width, in bits.",
+        "CARGO_CFG_TARGET_POINTER_WIDTH",
+    );
+    insert(BUILD_PROFILE, "The active Cargo profile.", "PROFILE");
+    insert(BUILD_OPT_LEVEL, "The active optimization level.", "OPT_LEVEL");
+
+    if let Some(debug) = std_env.get("DEBUG") {
+        let mut v = ConstVal::new_bool("Whether debug info is enabled. From `DEBUG`.");
+        v.v = debug.clone();
+        map.insert(BUILD_DEBUG, v);
+    }
+
+    map
+}
+
+pub const SHADOW_FEATURES: ShadowConst = "SHADOW_FEATURES";
+
+/// Build constant collecting the names of enabled Cargo features, derived from the
+/// `CARGO_FEATURE_*` variables Cargo passes to build scripts (one per enabled feature).
+pub fn new_features_env(std_env: &BTreeMap<String, String>) -> BTreeMap<ShadowConst, ConstVal> {
+    let mut map = BTreeMap::new();
+
+    let features: Vec<String> = std_env
+        .keys()
+        .filter_map(|k| k.strip_prefix("CARGO_FEATURE_"))
+        .map(|name| name.to_ascii_lowercase())
+        .collect();
+
+    let mut v = ConstVal::new_str_slice("The Cargo features enabled at build time.");
+    v.v = features.join(",");
+    map.insert(SHADOW_FEATURES, v);
+
+    map
+}
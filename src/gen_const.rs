@@ -0,0 +1,60 @@
+use crate::shadow::Shadow;
+use crate::{BRANCH, CARGO_CLIPPY_ALLOW_ALL, TAG};
+
+pub const BUILD_CONST_VERSION: &str = "VERSION";
+pub const BUILD_CONST_CLAP_LONG_VERSION: &str = "CLAP_LONG_VERSION";
+
+/// Generates a `version()` fn that reports the `git` tag, for projects built from a tag.
+pub fn version_tag_const() -> String {
+    format!(
+        "{CARGO_CLIPPY_ALLOW_ALL}\n\
+        pub const fn version() -> &'static str {{\n\
+        \t{TAG}\n\
+        }}\n"
+    )
+}
+
+/// Generates a `version()` fn that reports the `git` branch, for projects built off a branch.
+pub fn version_branch_const() -> String {
+    format!(
+        "{CARGO_CLIPPY_ALLOW_ALL}\n\
+        pub const fn version() -> &'static str {{\n\
+        \t{BRANCH}\n\
+        }}\n"
+    )
+}
+
+/// Generates a `clap_long_version()` fn suitable for `clap`'s `long_version`, for tag builds.
+pub fn clap_long_version_tag_const() -> String {
+    format!(
+        "{CARGO_CLIPPY_ALLOW_ALL}\n\
+        pub const fn clap_long_version() -> &'static str {{\n\
+        \t{TAG}\n\
+        }}\n"
+    )
+}
+
+/// Generates a `clap_long_version()` fn suitable for `clap`'s `long_version`, for branch builds.
+pub fn clap_long_version_branch_const() -> String {
+    format!(
+        "{CARGO_CLIPPY_ALLOW_ALL}\n\
+        pub const fn clap_long_version() -> &'static str {{\n\
+        \t{BRANCH}\n\
+        }}\n"
+    )
+}
+
+/// Generates a `cargo_metadata()` fn exposing the raw `cargo metadata` JSON, when available.
+pub fn cargo_metadata_fn(shadow: &Shadow) -> String {
+    let metadata = shadow
+        .std_env
+        .get("CARGO_METADATA")
+        .cloned()
+        .unwrap_or_default();
+    format!(
+        "{CARGO_CLIPPY_ALLOW_ALL}\n\
+        pub const fn cargo_metadata() -> &'static str {{\n\
+        \tr#\"{metadata}\"#\n\
+        }}\n"
+    )
+}
@@ -0,0 +1,71 @@
+use crate::build::ConstVal;
+use crate::ci::CiType;
+use crate::{BRANCH, COMMIT_HASH, TAG};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::process::Command;
+
+/// Runs `git` with the given arguments in `src_path`, returning its trimmed stdout on
+/// success. Returns `None` on any failure: `git` isn't installed, `src_path` isn't inside a
+/// `git` checkout, or the command itself failed (e.g. no tag points at `HEAD`).
+fn run_git(src_path: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(src_path)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    let trimmed = stdout.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+/// The current `git` branch, or `None` when `HEAD` isn't on a branch (e.g. a detached-HEAD
+/// checkout, which is common in CI).
+fn git_branch(src_path: &Path) -> Option<String> {
+    run_git(src_path, &["symbolic-ref", "--short", "-q", "HEAD"])
+}
+
+/// The tag that exactly matches `HEAD`, if any.
+fn git_tag(src_path: &Path) -> Option<String> {
+    run_git(src_path, &["describe", "--tags", "--exact-match"])
+}
+
+/// The full `HEAD` commit hash.
+fn git_commit_hash(src_path: &Path) -> Option<String> {
+    run_git(src_path, &["rev-parse", "HEAD"])
+}
+
+/// Build constants derived from the repository's `git` state (branch, tag, commit hash, ...).
+///
+/// Shells out to `git` from `src_path`. Falls back to empty values when `src_path` isn't
+/// inside a `git` checkout (e.g. when building from a packaged crate tarball), `git` isn't
+/// installed, or the particular piece of information doesn't apply (e.g. no tag points at
+/// `HEAD`).
+pub fn new_git(
+    src_path: &Path,
+    _ci_type: CiType,
+    _std_env: &BTreeMap<String, String>,
+) -> BTreeMap<&'static str, ConstVal> {
+    let mut map = BTreeMap::new();
+
+    let mut branch = ConstVal::new("The current `git` branch.");
+    branch.v = git_branch(src_path).unwrap_or_default();
+    map.insert(BRANCH, branch);
+
+    let mut tag = ConstVal::new("The current `git` tag, if any.");
+    tag.v = git_tag(src_path).unwrap_or_default();
+    map.insert(TAG, tag);
+
+    let mut commit_hash = ConstVal::new("The current `git` commit hash.");
+    commit_hash.v = git_commit_hash(src_path).unwrap_or_default();
+    map.insert(COMMIT_HASH, commit_hash);
+
+    map
+}
@@ -0,0 +1,210 @@
+//! `shadow-rs` is a build-time information collector for Rust projects.
+//!
+//! Calling [`Shadow::build`] (typically from `build.rs`) inspects the project's `git`
+//! metadata, environment, and build configuration, then writes a `shadow.rs` file full of
+//! `pub const` definitions that can be `include!`d (via the [`shadow!`] macro) into the
+//! final binary.
+
+mod build;
+mod ci;
+mod date_time;
+mod env;
+mod gen_const;
+mod git;
+mod shadow;
+
+pub use build::{ConstType, ConstVal};
+pub use ci::CiType;
+pub use date_time::DateTime;
+pub use shadow::{SidecarFormat, Shadow};
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+
+/// A build constant's identifier, e.g. `"BRANCH"` or `"COMMIT_HASH"`.
+pub type ShadowConst = &'static str;
+
+/// The result type used throughout `shadow-rs`.
+pub type SdResult<T> = Result<T, Box<dyn Error>>;
+
+/// `#[allow(...)]` attribute attached to every generated constant so that consumers with
+/// stricter clippy lints enabled don't get tripped up by `shadow-rs`'s generated code.
+pub const CARGO_CLIPPY_ALLOW_ALL: &str = "#[allow(clippy::all)]";
+
+pub const TAG: ShadowConst = "TAG";
+pub const BRANCH: ShadowConst = "BRANCH";
+pub const COMMIT_HASH: ShadowConst = "COMMIT_HASH";
+pub const CARGO_TREE: ShadowConst = "CARGO_TREE";
+pub const CARGO_METADATA: ShadowConst = "CARGO_METADATA";
+
+/// Collects the build-relevant environment variables via [`std::env::vars`].
+///
+/// This is the single point of truth other subsystems (git, CI, project, system env) read
+/// from, so that the whole build only ever takes one snapshot of the environment.
+pub fn get_std_env() -> BTreeMap<String, String> {
+    std::env::vars().collect()
+}
+
+/// Determines when Cargo should be told to re-run the build script.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildPattern {
+    /// Only rerun when `Cargo.toml` or the `.git` HEAD changes.
+    Lazy,
+    /// Rerun on every build: emits `cargo:rerun-if-changed` for the output directory, which
+    /// each run rewrites, plus `cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH`.
+    RealTime,
+    /// Rerun when any of the given paths change.
+    Custom(Vec<String>),
+}
+
+impl Default for BuildPattern {
+    fn default() -> Self {
+        BuildPattern::Lazy
+    }
+}
+
+impl BuildPattern {
+    pub(crate) fn rerun_if<'a>(&self, _keys: impl Iterator<Item = &'a ShadowConst>, out_dir: &str) {
+        match self {
+            BuildPattern::Lazy => {
+                println!("cargo:rerun-if-changed=.git/HEAD");
+            }
+            BuildPattern::RealTime => {
+                println!("cargo:rerun-if-changed={out_dir}");
+                println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+            }
+            BuildPattern::Custom(paths) => {
+                for p in paths {
+                    println!("cargo:rerun-if-changed={p}");
+                }
+            }
+        }
+    }
+}
+
+/// Optional hook, run after `shadow-rs` has written its own constants, that can append
+/// additional hand-written constants to the same `shadow.rs` file.
+pub struct Hook(Box<dyn Fn(&std::fs::File) -> SdResult<()>>);
+
+impl Hook {
+    pub fn new<F>(f: F) -> Hook
+    where
+        F: Fn(&std::fs::File) -> SdResult<()> + 'static,
+    {
+        Hook(Box::new(f))
+    }
+
+    pub(crate) fn hook_inner(&self) -> &dyn Fn(&std::fs::File) -> SdResult<()> {
+        &self.0
+    }
+}
+
+/// Builder used to configure and run the `shadow-rs` build step.
+///
+/// See [`Shadow`] for the fields this eventually populates.
+#[derive(Default)]
+pub struct ShadowBuilder {
+    src_path: Option<String>,
+    out_path: Option<String>,
+    build_pattern: BuildPattern,
+    deny_const: BTreeSet<ShadowConst>,
+    hook: Option<Hook>,
+    sidecar_format: Option<shadow::SidecarFormat>,
+}
+
+impl ShadowBuilder {
+    pub fn builder() -> ShadowBuilder {
+        ShadowBuilder::default()
+    }
+
+    pub fn src_path<S: Into<String>>(mut self, path: S) -> Self {
+        self.src_path = Some(path.into());
+        self
+    }
+
+    pub fn out_path<S: Into<String>>(mut self, path: S) -> Self {
+        self.out_path = Some(path.into());
+        self
+    }
+
+    pub fn build_pattern(mut self, pattern: BuildPattern) -> Self {
+        self.build_pattern = pattern;
+        self
+    }
+
+    pub fn deny_const(mut self, deny_const: BTreeSet<ShadowConst>) -> Self {
+        self.deny_const = deny_const;
+        self
+    }
+
+    pub fn hook(mut self, hook: Hook) -> Self {
+        self.hook = Some(hook);
+        self
+    }
+
+    /// Additionally write build metadata as a `shadow.json`/`shadow.toml` sidecar file
+    /// alongside `shadow.rs`, so tooling that isn't Rust-aware can consume it.
+    pub fn sidecar_format(mut self, format: shadow::SidecarFormat) -> Self {
+        self.sidecar_format = Some(format);
+        self
+    }
+
+    pub(crate) fn get_src_path(&self) -> SdResult<String> {
+        match &self.src_path {
+            Some(p) => Ok(p.clone()),
+            None => Ok(std::env::var("CARGO_MANIFEST_DIR")?),
+        }
+    }
+
+    pub(crate) fn get_out_path(&self) -> SdResult<&str> {
+        match &self.out_path {
+            Some(p) => Ok(p.as_str()),
+            None => Ok(Box::leak(std::env::var("OUT_DIR")?.into_boxed_str())),
+        }
+    }
+
+    pub(crate) fn get_build_pattern(&self) -> &BuildPattern {
+        &self.build_pattern
+    }
+
+    pub(crate) fn get_deny_const(&self) -> &BTreeSet<ShadowConst> {
+        &self.deny_const
+    }
+
+    pub(crate) fn get_hook(&self) -> Option<&Hook> {
+        self.hook.as_ref()
+    }
+
+    pub(crate) fn get_sidecar_format(&self) -> Option<shadow::SidecarFormat> {
+        self.sidecar_format
+    }
+
+    pub fn build(self) -> SdResult<Shadow> {
+        Shadow::build_inner(self)
+    }
+}
+
+impl Shadow {
+    /// Convenience entry point equivalent to
+    /// `ShadowBuilder::builder().src_path(src_path).out_path(out_path).build()`.
+    pub fn build(src_path: String, out_path: String) -> SdResult<Shadow> {
+        ShadowBuilder::builder()
+            .src_path(src_path)
+            .out_path(out_path)
+            .build()
+    }
+}
+
+/// Brings the constants written to `shadow.rs` into scope under the given module name.
+///
+/// ```ignore
+/// shadow_rs::shadow!(build);
+/// ```
+#[macro_export]
+macro_rules! shadow {
+    ($mod_name:ident) => {
+        pub mod $mod_name {
+            include!(concat!(env!("OUT_DIR"), "/shadow.rs"));
+        }
+    };
+}
@@ -1,6 +1,6 @@
 use crate::build::{ConstType, ConstVal};
-use crate::ci::CiType;
-use crate::env::{new_project, new_system_env};
+use crate::ci::{detect_ci, new_ci_const, CiType};
+use crate::env::{new_compile_env, new_features_env, new_project, new_system_env};
 use crate::gen_const::{
     clap_long_version_branch_const, clap_long_version_tag_const, version_branch_const,
     version_tag_const, BUILD_CONST_CLAP_LONG_VERSION, BUILD_CONST_VERSION,
@@ -8,7 +8,7 @@ use crate::gen_const::{
 use crate::git::new_git;
 use crate::{
     get_std_env, BuildPattern, DateTime, SdResult, ShadowBuilder, ShadowConst,
-    CARGO_CLIPPY_ALLOW_ALL, TAG,
+    CARGO_CLIPPY_ALLOW_ALL, BRANCH, TAG,
 };
 use std::collections::{BTreeMap, BTreeSet};
 use std::fs::File;
@@ -17,6 +17,55 @@ use std::path::Path;
 
 pub(crate) const DEFINE_SHADOW_RS: &str = "shadow.rs";
 
+/// The format of the optional machine-readable sidecar file written next to `shadow.rs`.
+///
+/// Configured via [`ShadowBuilder::sidecar_format`]. Downstream tooling (release pipelines,
+/// SBOM generators, packaging scripts) can read this instead of parsing generated Rust.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SidecarFormat {
+    /// Write `shadow.json`.
+    Json,
+    /// Write `shadow.toml`.
+    Toml,
+}
+
+impl SidecarFormat {
+    fn file_name(self) -> &'static str {
+        match self {
+            SidecarFormat::Json => "shadow.json",
+            SidecarFormat::Toml => "shadow.toml",
+        }
+    }
+}
+
+/// Renders `s` as a quoted JSON/TOML basic string, escaping control characters the way
+/// both formats require: `\"`, `\\`, the named escapes for the common whitespace
+/// controls, and `\u00XX` (exactly four hex digits, no braces) for every other control
+/// character - both the C0 range below `0x20` and `0x7F` (DEL), which TOML's basic-string
+/// grammar also requires escaped. Rust's `{:?}` Debug formatting is NOT equivalent here -
+/// it emits variable-width `\u{1}`, which neither JSON nor TOML parsers accept.
+fn escape_str(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            c if (c as u32) < 0x20 || c as u32 == 0x7F => {
+                out.push_str(&format!("\\u{:04x}", c as u32))
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
 /// `shadow-rs` configuration.
 ///
 /// This struct encapsulates the configuration for the `shadow-rs` build process. It allows for fine-grained control over
@@ -79,6 +128,10 @@ pub struct Shadow {
     /// This field sets the pattern for how often the package should be rebuilt. Options include `Lazy`, `RealTime`, and `Custom`, each with its own implications on the build frequency and conditions under which a rebuild is triggered.
     /// It can be configured using [`ShadowBuilder::build_pattern`].
     pub build_pattern: BuildPattern,
+
+    /// The format of the optional `shadow.json`/`shadow.toml` sidecar file, if requested
+    /// via [`ShadowBuilder::sidecar_format`].
+    pub sidecar_format: Option<SidecarFormat>,
 }
 
 impl Shadow {
@@ -94,23 +147,10 @@ impl Shadow {
         Ok(())
     }
 
-    /// Try to infer the CI system that we're currently running under.
-    ///
-    /// TODO: Recognize other CI types, especially Travis and Jenkins.
+    /// Infer the CI system that we're currently running under, via the table-driven
+    /// signatures in [`crate::ci`].
     fn try_ci(&self) -> CiType {
-        if let Some(c) = self.std_env.get("GITLAB_CI") {
-            if c == "true" {
-                return CiType::Gitlab;
-            }
-        }
-
-        if let Some(c) = self.std_env.get("GITHUB_ACTIONS") {
-            if c == "true" {
-                return CiType::Github;
-            }
-        }
-
-        CiType::None
+        detect_ci(&self.std_env)
     }
 
     /// Checks if the specified build constant is in the deny list.
@@ -129,6 +169,7 @@ impl Shadow {
         let src_path = builder.get_src_path()?;
         let build_pattern = builder.get_build_pattern().clone();
         let deny_const = builder.get_deny_const().clone();
+        let sidecar_format = builder.get_sidecar_format();
 
         let out = {
             let path = Path::new(out_path);
@@ -146,6 +187,7 @@ impl Shadow {
             deny_const,
             out_path: out_path.to_string(),
             build_pattern,
+            sidecar_format,
         };
         shadow.std_env = get_std_env();
 
@@ -153,12 +195,21 @@ impl Shadow {
         let src_path = Path::new(src_path.as_str());
 
         let mut map = new_git(src_path, ci_type, &shadow.std_env);
+        for (k, v) in new_ci_const(ci_type, &shadow.std_env) {
+            map.insert(k, v);
+        }
         for (k, v) in new_project(&shadow.std_env) {
             map.insert(k, v);
         }
         for (k, v) in new_system_env(&shadow) {
             map.insert(k, v);
         }
+        for (k, v) in new_compile_env(&shadow.std_env) {
+            map.insert(k, v);
+        }
+        for (k, v) in new_features_env(&shadow.std_env) {
+            map.insert(k, v);
+        }
         shadow.map = map;
 
         // deny const
@@ -190,6 +241,109 @@ impl Shadow {
 
         self.gen_build_in(gen_version)?;
 
+        if let Some(format) = self.sidecar_format {
+            let tag_or_branch = self.map.get(TAG).filter(|t| !t.v.is_empty()).or(self.map.get(BRANCH));
+            let version = tag_or_branch.map(|v| v.v.clone());
+            // `version()` and `clap_long_version()` are both derived from the same
+            // tag-or-branch value `gen_version` picked; see `gen_const.rs`.
+            let clap_long_version = version.clone();
+            self.write_sidecar(format, version, clap_long_version)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes every entry in [`Shadow::map`] to a `shadow.json`/`shadow.toml` sidecar file
+    /// next to `shadow.rs`, so tooling that doesn't want to parse generated Rust can read
+    /// build metadata directly. Iterates the same `BTreeMap` `gen_const` does, so ordering
+    /// is deterministic between the two files. `version`/`clap_long_version` are the same
+    /// derived strings that `gen_version` embeds into `shadow.rs`'s `version()` and
+    /// `clap_long_version()` fns.
+    fn write_sidecar(
+        &self,
+        format: SidecarFormat,
+        version: Option<String>,
+        clap_long_version: Option<String>,
+    ) -> SdResult<()> {
+        let path = Path::new(&self.out_path).join(format.file_name());
+        let mut f = File::create(path)?;
+
+        match format {
+            SidecarFormat::Json => {
+                let mut out = String::from("{\n");
+                if let Some(version) = &version {
+                    out.push_str(&format!("  \"version\": {},\n", escape_str(version)));
+                }
+                if let Some(clap_long_version) = &clap_long_version {
+                    out.push_str(&format!(
+                        "  \"clap_long_version\": {},\n",
+                        escape_str(clap_long_version)
+                    ));
+                }
+                let mut entries = self.map.iter().peekable();
+                while let Some((k, v)) = entries.next() {
+                    let value_json = match v.t {
+                        ConstType::Slice => {
+                            let bytes: Vec<String> =
+                                v.v.as_bytes().iter().map(u8::to_string).collect();
+                            format!("[{}]", bytes.join(","))
+                        }
+                        ConstType::StrSlice => {
+                            let items: Vec<String> = v
+                                .v
+                                .split(',')
+                                .filter(|s| !s.is_empty())
+                                .map(escape_str)
+                                .collect();
+                            format!("[{}]", items.join(","))
+                        }
+                        ConstType::Str | ConstType::Bool => escape_str(&v.v),
+                    };
+                    out.push_str(&format!(
+                        "  \"{k}\": {{\"value\": {value_json}, \"type\": {}, \"desc\": {}}}",
+                        escape_str(&v.t.to_string()),
+                        escape_str(&v.desc)
+                    ));
+                    out.push_str(if entries.peek().is_some() { ",\n" } else { "\n" });
+                }
+                out.push_str("}\n");
+                write!(&mut f, "{out}")?;
+            }
+            SidecarFormat::Toml => {
+                if let Some(version) = &version {
+                    writeln!(&mut f, "version = {}", escape_str(version))?;
+                }
+                if let Some(clap_long_version) = &clap_long_version {
+                    writeln!(&mut f, "clap_long_version = {}\n", escape_str(clap_long_version))?;
+                } else if version.is_some() {
+                    writeln!(&mut f)?;
+                }
+                for (k, v) in &self.map {
+                    let value_toml = match v.t {
+                        ConstType::Slice => {
+                            let bytes: Vec<String> =
+                                v.v.as_bytes().iter().map(u8::to_string).collect();
+                            format!("[{}]", bytes.join(", "))
+                        }
+                        ConstType::StrSlice => {
+                            let items: Vec<String> = v
+                                .v
+                                .split(',')
+                                .filter(|s| !s.is_empty())
+                                .map(escape_str)
+                                .collect();
+                            format!("[{}]", items.join(", "))
+                        }
+                        ConstType::Str | ConstType::Bool => escape_str(&v.v),
+                    };
+                    writeln!(&mut f, "[{k}]")?;
+                    writeln!(&mut f, "value = {value_toml}")?;
+                    writeln!(&mut f, "type = {}", escape_str(&v.t.to_string()))?;
+                    writeln!(&mut f, "desc = {}\n", escape_str(&v.desc))?;
+                }
+            }
+        }
+
         Ok(())
     }
 
@@ -209,12 +363,22 @@ impl Shadow {
 // Author: https://www.github.com/baoyachi
 // Generation time: {}
 "#,
-            DateTime::now().to_rfc2822()
+            self.build_time().to_rfc2822()
         );
         writeln!(&self.f, "{desc}\n\n")?;
         Ok(())
     }
 
+    /// The instant to embed into time-derived output: `SOURCE_DATE_EPOCH`, when set, so that
+    /// packagers get bit-for-bit reproducible builds; otherwise the current wall-clock time.
+    pub(crate) fn build_time(&self) -> DateTime {
+        self.std_env
+            .get("SOURCE_DATE_EPOCH")
+            .and_then(|v| v.trim().parse::<i64>().ok())
+            .map(DateTime::from_unix_timestamp)
+            .unwrap_or_else(DateTime::now)
+    }
+
     fn write_const(&mut self, shadow_const: ShadowConst, val: ConstVal) -> SdResult<()> {
         let desc = format!("#[doc=r#\"{}\"#]", val.desc);
         let define = match val.t {
@@ -245,6 +409,18 @@ impl Shadow {
                 ConstType::Slice,
                 val.v.as_bytes()
             ),
+            ConstType::StrSlice => {
+                let items: Vec<&str> = val.v.split(',').filter(|s| !s.is_empty()).collect();
+                format!(
+                    "#[allow(dead_code)]\n\
+                {}\n\
+            pub const {} :{} = &{:?};",
+                    CARGO_CLIPPY_ALLOW_ALL,
+                    shadow_const.to_ascii_uppercase(),
+                    ConstType::StrSlice,
+                    items
+                )
+            }
         };
 
         writeln!(&self.f, "{desc}")?;
@@ -278,7 +454,7 @@ impl Shadow {
                 ConstType::Str | ConstType::Bool => {
                     format!(r#"{}println!("{k}:{{{k}}}\n");{}"#, "\t", "\n")
                 }
-                ConstType::Slice => {
+                ConstType::Slice | ConstType::StrSlice => {
                     format!(r#"{}println!("{k}:{{:?}}\n",{});{}"#, "\t", k, "\n",)
                 }
             };
@@ -286,7 +462,7 @@ impl Shadow {
         }
 
         // append gen fn
-        for k in gen_const {
+        for k in &gen_const {
             let tmp = format!(r#"{}println!("{k}:{{{k}}}\n");{}"#, "\t", "\n");
             print_val.push_str(tmp.as_str());
         }
@@ -306,10 +482,65 @@ impl Shadow {
 
             use crate::gen_const::cargo_metadata_fn;
             writeln!(&self.f, "{}", cargo_metadata_fn(self))?;
+
+            writeln!(&self.f, "{}", self.gen_build_info_struct(&gen_const))?;
         }
 
         Ok(())
     }
+
+    /// Generates a `BuildInfo` table that enumerates every constant `write_const` emitted,
+    /// plus the version fns from `gen_version`, as name/value/type/description tuples. This
+    /// gives consumers structured, runtime-iterable access to build metadata (e.g. to
+    /// serialize into an HTTP `/version` endpoint) instead of hand-referencing each symbol.
+    fn gen_build_info_struct(&self, gen_const: &[&'static str]) -> String {
+        let mut entries = String::new();
+        for (k, v) in &self.map {
+            entries.push_str(&format!(
+                "\tBuildConstEntry {{ name: {k:?}, value: {:?}, kind: {:?}, desc: {:?} }},\n",
+                v.v,
+                v.t.to_string(),
+                v.desc
+            ));
+        }
+        for k in gen_const {
+            // `gen_const` holds the display names (`BUILD_CONST_VERSION`, ...), not the
+            // lowercase fn identifiers `gen_const.rs` actually emits (`version`,
+            // `clap_long_version`) - translate before generating a call expression. The call
+            // is wrapped in an inline `const` block so the slice can be promoted to `'static`.
+            let fn_name = match *k {
+                BUILD_CONST_VERSION => "version",
+                BUILD_CONST_CLAP_LONG_VERSION => "clap_long_version",
+                other => other,
+            };
+            entries.push_str(&format!(
+                "\tBuildConstEntry {{ name: {k:?}, value: const {{ {fn_name}() }}, kind: \"&str\", desc: \"\" }},\n",
+            ));
+        }
+
+        format!(
+            "/// One build constant, as returned by `BuildInfo::entries()`.\n\
+            #[allow(dead_code)]\n\
+            {CARGO_CLIPPY_ALLOW_ALL}\n\
+            #[derive(Debug, Clone, Copy)]\n\
+            pub struct BuildConstEntry {{\n\
+            \tpub name: &'static str,\n\
+            \tpub value: &'static str,\n\
+            \tpub kind: &'static str,\n\
+            \tpub desc: &'static str,\n\
+            }}\n\n\
+            /// Typed, runtime-iterable view over every build constant `shadow-rs` emitted.\n\
+            #[allow(dead_code)]\n\
+            {CARGO_CLIPPY_ALLOW_ALL}\n\
+            pub struct BuildInfo;\n\n\
+            impl BuildInfo {{\n\
+            \t/// All build constants, in the same order they were written to this file.\n\
+            \tpub fn entries() -> &'static [BuildConstEntry] {{\n\
+            \t\t&[\n{entries}\t]\n\
+            \t}}\n\
+            }}\n",
+        )
+    }
 }
 
 #[cfg(test)]
@@ -353,4 +584,147 @@ mod tests {
             println!("K:{k},V:{v}");
         }
     }
+
+    #[test]
+    fn test_build_time_honors_source_date_epoch() -> SdResult<()> {
+        let mut std_env = BTreeMap::new();
+        std_env.insert("SOURCE_DATE_EPOCH".to_string(), "1700000000".to_string());
+        let shadow = Shadow {
+            f: File::create("test_build_time_honors_source_date_epoch.tmp")?,
+            map: Default::default(),
+            std_env,
+            deny_const: Default::default(),
+            out_path: "./".to_string(),
+            build_pattern: BuildPattern::default(),
+            sidecar_format: None,
+        };
+        assert_eq!(shadow.build_time().unix_timestamp(), 1_700_000_000);
+        fs::remove_file("test_build_time_honors_source_date_epoch.tmp")?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_time_falls_back_to_now_without_source_date_epoch() -> SdResult<()> {
+        let shadow = Shadow {
+            f: File::create("test_build_time_falls_back_to_now_without_source_date_epoch.tmp")?,
+            map: Default::default(),
+            std_env: Default::default(),
+            deny_const: Default::default(),
+            out_path: "./".to_string(),
+            build_pattern: BuildPattern::default(),
+            sidecar_format: None,
+        };
+        // No `SOURCE_DATE_EPOCH` set: falls back to `DateTime::now()`, which must at least
+        // be a plausible, positive Unix timestamp rather than the zero-value fallback.
+        assert!(shadow.build_time().unix_timestamp() > 0);
+        fs::remove_file("test_build_time_falls_back_to_now_without_source_date_epoch.tmp")?;
+        Ok(())
+    }
+
+    /// Scans a JSON string body (the bytes after the opening `"`) for the closing,
+    /// unescaped `"`, honoring `\"` and `\\` the way a real JSON parser would.
+    fn find_json_string_end(s: &str) -> usize {
+        let bytes = s.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'\\' => i += 2,
+                b'"' => return i,
+                _ => i += 1,
+            }
+        }
+        panic!("unterminated JSON string in {s:?}");
+    }
+
+    /// Decodes the JSON escapes `write_sidecar`/`escape_str` produce, so a test can assert
+    /// the sidecar actually round-trips rather than just "looks non-empty".
+    fn json_unescape(s: &str) -> String {
+        let mut out = String::new();
+        let mut chars = s.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                out.push(c);
+                continue;
+            }
+            match chars.next().expect("dangling escape") {
+                '"' => out.push('"'),
+                '\\' => out.push('\\'),
+                'n' => out.push('\n'),
+                'r' => out.push('\r'),
+                't' => out.push('\t'),
+                'b' => out.push('\u{8}'),
+                'f' => out.push('\u{c}'),
+                'u' => {
+                    let hex: String = (0..4).map(|_| chars.next().expect("short \\u escape")).collect();
+                    let code = u32::from_str_radix(&hex, 16).expect("valid \\u hex");
+                    out.push(char::from_u32(code).expect("valid codepoint"));
+                }
+                other => panic!("unexpected escape \\{other}"),
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_write_sidecar_json_roundtrips_control_characters() -> SdResult<()> {
+        let mut map = BTreeMap::new();
+        let mut v = ConstVal::new("desc with \"quotes\" and a tab\there");
+        v.v = "value\u{1}with\nnewline\tand\ttabs\u{7f}end".to_string();
+        map.insert("TEST_CONST", v);
+
+        let out_dir = "test_write_sidecar_json_roundtrips_control_characters";
+        fs::create_dir_all(out_dir)?;
+        let shadow = Shadow {
+            f: File::create(format!("{out_dir}/shadow.rs"))?,
+            map,
+            std_env: Default::default(),
+            deny_const: Default::default(),
+            out_path: out_dir.to_string(),
+            build_pattern: BuildPattern::default(),
+            sidecar_format: Some(SidecarFormat::Json),
+        };
+        shadow.write_sidecar(SidecarFormat::Json, None, None)?;
+
+        let json = fs::read_to_string(format!("{out_dir}/shadow.json"))?;
+        let marker = "\"TEST_CONST\": {\"value\": \"";
+        let start = json.find(marker).expect("TEST_CONST entry present") + marker.len();
+        let end = start + find_json_string_end(&json[start..]);
+        assert_eq!(
+            json_unescape(&json[start..end]),
+            "value\u{1}with\nnewline\tand\ttabs\u{7f}end"
+        );
+
+        fs::remove_dir_all(out_dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_sidecar_toml_roundtrips_control_characters() -> SdResult<()> {
+        let mut map = BTreeMap::new();
+        let mut v = ConstVal::new("plain desc");
+        v.v = "value\u{1}with\nnewline\u{7f}end".to_string();
+        map.insert("TEST_CONST", v);
+
+        let out_dir = "test_write_sidecar_toml_roundtrips_control_characters";
+        fs::create_dir_all(out_dir)?;
+        let shadow = Shadow {
+            f: File::create(format!("{out_dir}/shadow.rs"))?,
+            map,
+            std_env: Default::default(),
+            deny_const: Default::default(),
+            out_path: out_dir.to_string(),
+            build_pattern: BuildPattern::default(),
+            sidecar_format: Some(SidecarFormat::Toml),
+        };
+        shadow.write_sidecar(SidecarFormat::Toml, None, None)?;
+
+        let toml = fs::read_to_string(format!("{out_dir}/shadow.toml"))?;
+        let marker = "value = \"";
+        let start = toml.find(marker).expect("value line present") + marker.len();
+        let end = start + find_json_string_end(&toml[start..]);
+        assert_eq!(json_unescape(&toml[start..end]), "value\u{1}with\nnewline\u{7f}end");
+
+        fs::remove_dir_all(out_dir)?;
+        Ok(())
+    }
 }